@@ -5,6 +5,7 @@ use super::used_size::UsedSize;
 use std::collections::BTreeMap;
 use std::hash::Hash;
 
+use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 use strum_macros::{EnumIter, EnumCount, AsRefStr};
 
@@ -12,6 +13,36 @@ use polars::prelude::*;
 
 const INVALID_VALUE: f64 = -1.0e6;
 
+///Configuration for the SPS focal plane detector geometry and delay-line calibration.
+///
+///Holds the per-run constants needed to turn delay-line timing differences into
+///positions (`X1`/`X2`), the wire-plane separation used for `Theta`, and the
+///weights used to combine `X1`/`X2` into `Xavg`. Load this from a serialized
+///config file so data from different spectrograph setups or re-tuned delay
+///lines can be analyzed without recompiling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SPSConfig {
+    ///Drift velocity factor for the front (X1) delay line
+    pub x1_drift: f64,
+    ///Drift velocity factor for the back (X2) delay line
+    pub x2_drift: f64,
+    ///Separation between the X1 and X2 wire planes, used to compute Theta
+    pub wire_separation: f64,
+    ///Weights (w1, w2) combining X1 and X2 into Xavg; None disables Xavg
+    pub xavg_weights: Option<(f64, f64)>,
+}
+
+impl Default for SPSConfig {
+    fn default() -> Self {
+        SPSConfig {
+            x1_drift: 1.0 / 2.1,
+            x2_drift: 1.0 / 1.98,
+            wire_separation: 36.0,
+            xavg_weights: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Hash, Eq, PartialOrd, Ord, PartialEq, EnumIter, EnumCount, AsRefStr)]
 pub enum SPSDataField {
     AnodeFrontEnergy,
@@ -127,7 +158,7 @@ impl SPSData {
         }
     }
 
-    pub fn append_event(&mut self, event: Vec<CompassData>, map: &ChannelMap, weights: Option<(f64, f64)>) {
+    pub fn append_event(&mut self, event: Vec<CompassData>, map: &ChannelMap, config: &SPSConfig) {
 
         self.rows += 1;
         self.push_defaults();
@@ -254,24 +285,24 @@ impl SPSData {
         let mut x1 = INVALID_VALUE;
         let mut x2 = INVALID_VALUE;
         if dfr_time != INVALID_VALUE && dfl_time != INVALID_VALUE {
-            x1 = (dfl_time - dfr_time) * 0.5 * 1.0/2.1;
+            x1 = (dfl_time - dfr_time) * 0.5 * config.x1_drift;
             self.set_value(&SPSDataField::X1, x1);
         }
         if dbr_time != INVALID_VALUE && dbl_time != INVALID_VALUE {
-            x2 = (dbl_time - dbr_time) * 0.5 * 1.0/1.98;
+            x2 = (dbl_time - dbr_time) * 0.5 * config.x2_drift;
             self.set_value(&SPSDataField::X2, x2);
         }
         if x1 != INVALID_VALUE && x2 != INVALID_VALUE {
             let diff = x2 -x1;
             if diff > 0.0 {
-                self.set_value(&SPSDataField::Theta, (diff/36.0).atan());
+                self.set_value(&SPSDataField::Theta, (diff/config.wire_separation).atan());
             } else if diff < 0.0 {
-                self.set_value(&SPSDataField::Theta, std::f64::consts::PI + (diff/36.0).atan());
+                self.set_value(&SPSDataField::Theta, std::f64::consts::PI + (diff/config.wire_separation).atan());
             } else {
                 self.set_value(&SPSDataField::Theta, std::f64::consts::PI * 0.5);
             }
 
-            match weights {
+            match config.xavg_weights {
                Some(w) => self.set_value(&SPSDataField::Xavg, w.0 * x1 + w.1 * x2),
                None => self.set_value(&SPSDataField::Xavg, INVALID_VALUE)
             };